@@ -0,0 +1,238 @@
+//! Persistent daemon that holds a single sway IPC connection and answers
+//! window-state queries from the `spawn` CLI over a Unix socket, instead of
+//! every invocation re-walking the whole tree via `swaymsg`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use sway_spawn::config::Spawn;
+use sway_spawn::ipc::{socket_path, Request, Response};
+use sway_spawn::sway_ipc::SwayIpc;
+use sway_spawn::window::{extract_windows, SwayWindow};
+
+/// Everything the daemon tracks about the current window state.
+#[derive(Default)]
+struct DaemonState {
+    windows: Vec<SwayWindow>,
+    /// Window ids in focus order, most recently focused first.
+    focus_history: Vec<i64>,
+}
+
+type SharedState = Arc<Mutex<DaemonState>>;
+type SharedControl = Arc<Mutex<SwayIpc>>;
+
+fn refresh_windows(control: &mut SwayIpc, state: &SharedState) -> std::io::Result<()> {
+    let tree = control.get_tree()?;
+    let mut extracted = Vec::new();
+    extract_windows(&tree, &mut extracted);
+    state.lock().unwrap().windows = extracted;
+    Ok(())
+}
+
+/// Listen for `window`/`workspace` events on a dedicated connection, keeping
+/// `state` up to date and driving auto-tiling. Runs for the lifetime of the
+/// daemon.
+fn watch_events(mut events: SwayIpc, mut control: SwayIpc, state: SharedState, config: Spawn) {
+    if let Err(e) = events.subscribe(&["window", "workspace"]) {
+        eprintln!("spawn-daemon: failed to subscribe to sway events: {}", e);
+        return;
+    }
+
+    loop {
+        match events.recv() {
+            Ok((_, payload)) => {
+                let change = payload.get("change").and_then(|c| c.as_str());
+                let container_id = payload
+                    .get("container")
+                    .and_then(|c| c.get("id"))
+                    .and_then(|i| i.as_i64());
+
+                if change == Some("focus") {
+                    if let Some(id) = container_id {
+                        let mut s = state.lock().unwrap();
+                        s.focus_history.retain(|&existing| existing != id);
+                        s.focus_history.insert(0, id);
+                    }
+                }
+
+                if let Err(e) = refresh_windows(&mut control, &state) {
+                    eprintln!("spawn-daemon: failed to refresh window list: {}", e);
+                }
+
+                if config.auto_tile && matches!(change, Some("focus") | Some("new")) {
+                    if let Some(id) = container_id {
+                        auto_tile(&mut control, &state, &config, id);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("spawn-daemon: lost connection to sway: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+/// Split the container that just became focused/new horizontally or
+/// vertically to match its own aspect ratio, so new children land beside
+/// the wider dimension instead of always using sway's default direction.
+fn auto_tile(control: &mut SwayIpc, state: &SharedState, config: &Spawn, id: i64) {
+    let window = {
+        let s = state.lock().unwrap();
+        match s.windows.iter().find(|w| w.id == id) {
+            Some(w) => w.clone(),
+            None => return,
+        }
+    };
+
+    // Floating windows and stacked/tabbed containers manage their own
+    // layout; leave them alone.
+    if window.window_type == "floating_con" {
+        return;
+    }
+    if matches!(window.parent_layout.as_deref(), Some("stacked") | Some("tabbed")) {
+        return;
+    }
+
+    if let Some(output) = &window.output {
+        if config.auto_tile_exclude_outputs.iter().any(|o| o == output) {
+            return;
+        }
+    }
+    if let Some(workspace) = &window.workspace {
+        if config
+            .auto_tile_exclude_workspaces
+            .iter()
+            .any(|w| w == workspace)
+        {
+            return;
+        }
+    }
+
+    let direction = if window.rect.width >= window.rect.height {
+        "splith"
+    } else {
+        "splitv"
+    };
+
+    if let Err(e) = control.run_command(direction) {
+        eprintln!("spawn-daemon: failed to auto-tile window {}: {}", id, e);
+    }
+}
+
+/// Target of the LRU and urgent switches: with `prefer_urgent`, an unfocused
+/// urgent window takes priority; otherwise, or when none is urgent, the
+/// most-recently-used live window after the head of the focus history.
+fn pick_switch_target(state: &DaemonState, prefer_urgent: bool) -> Option<i64> {
+    let live = |id: &i64| state.windows.iter().any(|w| w.id == *id);
+
+    if prefer_urgent {
+        if let Some(urgent) = state.windows.iter().find(|w| w.urgent && !w.focused) {
+            return Some(urgent.id);
+        }
+    }
+
+    state
+        .focus_history
+        .iter()
+        .filter(|id| live(id))
+        .nth(1)
+        .copied()
+}
+
+fn switch_to(control: &SharedControl, target: i64) -> Response {
+    let command = format!("[con_id={}] focus", target);
+    match control.lock().unwrap().run_command(&command) {
+        Ok(()) => Response::Ok,
+        Err(e) => Response::Error(format!("failed to focus window {}: {}", target, e)),
+    }
+}
+
+fn handle_client(
+    stream: UnixStream,
+    state: SharedState,
+    control: SharedControl,
+) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::ListWindows) => Response::Windows(state.lock().unwrap().windows.clone()),
+            Ok(Request::SwitchLru) => match pick_switch_target(&state.lock().unwrap(), false) {
+                Some(target) => switch_to(&control, target),
+                None => Response::Error("no previous window to switch to".to_string()),
+            },
+            Ok(Request::SwitchUrgent) => match pick_switch_target(&state.lock().unwrap(), true) {
+                Some(target) => switch_to(&control, target),
+                None => Response::Error("no urgent or previous window to switch to".to_string()),
+            },
+            Err(e) => Response::Error(format!("malformed request: {}", e)),
+        };
+
+        let mut payload = serde_json::to_string(&response)?;
+        payload.push('\n');
+        writer.write_all(payload.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let config = Spawn::new().unwrap_or_else(|e| {
+        eprintln!("spawn-daemon: could not load spawn.toml ({e}); auto-tile stays off");
+        Spawn::default()
+    });
+
+    let connect_or_exit = || {
+        SwayIpc::connect().unwrap_or_else(|e| {
+            eprintln!("spawn-daemon: cannot reach sway ({e}); is $SWAYSOCK set?");
+            std::process::exit(1);
+        })
+    };
+
+    let mut control = connect_or_exit();
+    // The sway-ipc wire protocol has no request IDs, so a `try_clone`'d
+    // socket sharing `control`'s read/write position can interleave partial
+    // frames with it once both are driven from different threads. Open a
+    // genuinely separate connection for the event watcher instead.
+    let events = connect_or_exit();
+    // Likewise, the command connection used by client threads (`switch_to`)
+    // must not share a socket with `control`: a client calling `spawn lru`
+    // while `watch_events` is mid-`get_tree`/`run_command` on `control` could
+    // otherwise corrupt both sides' framing and wedge the event loop for
+    // good, since there's no mutex spanning both connections.
+    let commands: SharedControl = Arc::new(Mutex::new(connect_or_exit()));
+
+    let state: SharedState = Arc::new(Mutex::new(DaemonState::default()));
+    refresh_windows(&mut control, &state)?;
+
+    thread::spawn({
+        let state = Arc::clone(&state);
+        move || watch_events(events, control, state, config)
+    });
+
+    let path = socket_path();
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let state = Arc::clone(&state);
+        let commands = Arc::clone(&commands);
+        thread::spawn(move || {
+            if let Err(e) = handle_client(stream, state, commands) {
+                eprintln!("spawn-daemon: client error: {}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+//! Config loading and the `Spawn` scratchpad/window-matching logic, shared
+//! between the CLI client and the daemon (which needs the auto-tile
+//! settings).
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::io::{Read, Write};
+use std::process::{Command, Output, Stdio};
+
+use crate::ipc::{query_daemon_autostart, Request, Response};
+use crate::window::SwayWindow;
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum WindowIdentifier {
+    Title(String),
+    AppId(String),
+    Class(String),
+}
+
+/// match against all WindowIdentifier wether the window fits the identifier
+pub fn matches_identifier(w: &SwayWindow, identifier: &WindowIdentifier) -> bool {
+    match identifier {
+        WindowIdentifier::Title(title) => w
+            .title
+            .as_ref()
+            .map(|t| t.eq_ignore_ascii_case(title))
+            .unwrap_or(false),
+        WindowIdentifier::AppId(app_id) => w
+            .app_id
+            .as_ref()
+            .map(|a| a.eq_ignore_ascii_case(app_id))
+            .unwrap_or(false),
+        WindowIdentifier::Class(class) => w.window_properties.as_ref().map_or(false, |wp| {
+            wp.class
+                .as_ref()
+                .map(|c| c.eq_ignore_ascii_case(class))
+                .unwrap_or(false)
+        }),
+    }
+}
+
+/// A window matches a set of identifiers if it satisfies any one of them,
+/// so an app whose window sometimes reports `app_id` and sometimes only
+/// `class` can still be matched reliably.
+pub fn matches_any_identifier(w: &SwayWindow, identifiers: &[WindowIdentifier]) -> bool {
+    identifiers.iter().any(|id| matches_identifier(w, id))
+}
+
+/// Accept either a single identifier or a list of them in `spawn.toml`:
+/// `identifier = { Title = "fish" }` or
+/// `identifier = [{ Title = "fish" }, { AppId = "fish" }]`.
+fn deserialize_identifiers<'de, D>(deserializer: D) -> Result<Vec<WindowIdentifier>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(WindowIdentifier),
+        Many(Vec<WindowIdentifier>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(identifier) => vec![identifier],
+        OneOrMany::Many(identifiers) => identifiers,
+    })
+}
+
+/// Build a best-effort identifier for an arbitrary window, for modes like
+/// `pick` that act on windows not tied to a configured app.
+pub fn identifier_for_window(w: &SwayWindow) -> WindowIdentifier {
+    if let Some(app_id) = &w.app_id {
+        return WindowIdentifier::AppId(app_id.clone());
+    }
+    if let Some(class) = w.window_properties.as_ref().and_then(|wp| wp.class.clone()) {
+        return WindowIdentifier::Class(class);
+    }
+    WindowIdentifier::Title(w.title.clone().unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    /// The command to execute
+    pub command: String,
+    /// Whether this is a terminal application
+    pub is_terminal: bool,
+    /// One or more window identifiers; the app is considered matched if any
+    /// of them matches.
+    #[serde(deserialize_with = "deserialize_identifiers")]
+    pub identifier: Vec<WindowIdentifier>,
+    /// Optional custom startup command override
+    pub startup_override: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Spawn {
+    pub terminal: String,
+    /// Command used to prompt for a window in `pick` mode. Must read
+    /// newline-separated choices from stdin and write the chosen one to
+    /// stdout (e.g. `wofi --dmenu`, `rofi -dmenu`, a dmenu/bemenu wrapper).
+    #[serde(default = "default_launcher")]
+    pub launcher: String,
+    /// Let `spawn-daemon` automatically splith/splitv the focused container
+    /// based on its aspect ratio whenever a window is created or focused.
+    #[serde(default)]
+    pub auto_tile: bool,
+    /// Output names to leave alone even when `auto_tile` is on.
+    #[serde(default)]
+    pub auto_tile_exclude_outputs: Vec<String>,
+    /// Workspace names to leave alone even when `auto_tile` is on.
+    #[serde(default)]
+    pub auto_tile_exclude_workspaces: Vec<String>,
+    pub apps: HashMap<String, AppConfig>,
+}
+
+fn default_launcher() -> String {
+    "wofi --dmenu".to_string()
+}
+
+/// Escape a value interpolated into a quoted sway criteria field
+/// (`[title="..."]`). Identifiers configured by the user in `spawn.toml` are
+/// trusted, but `pick`/`hint` build identifiers straight from live window
+/// titles/app_ids/classes, which are attacker-controlled text (e.g. a web
+/// page's document title) - without escaping, a title like
+/// `x"] exec evil #` closes the quoted string early and lets the rest of the
+/// title be parsed as further swaymsg commands. Escaping `\` and `"` keeps
+/// the whole value inside the quotes sway's parser expects.
+fn escape_criteria_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Example `spawn.toml` written out the first time `spawn` runs without a
+/// config file, so the tool is usable (and self-documenting) out of the box.
+const DEFAULT_CONFIG: &str = r#"# Configuration for `spawn` - see each field below for what it does.
+
+# Terminal emulator used to wrap terminal apps (needs to support `--title`
+# and `--command`, e.g. alacritty, foot, kitty).
+terminal = "alacritty"
+
+# Command used by `spawn pick` to choose a window. Must read
+# newline-separated choices from stdin and print the chosen line to stdout.
+launcher = "wofi --dmenu"
+
+# Have spawn-daemon auto-split new/focused containers based on their aspect
+# ratio instead of sway's default fixed split direction.
+# auto_tile = true
+# auto_tile_exclude_outputs = ["HDMI-A-1"]
+# auto_tile_exclude_workspaces = ["8"]
+
+# A terminal app, matched by the window title spawn gives it on startup.
+[apps.fish]
+command = "fish"
+is_terminal = true
+identifier = { Title = "fish" }
+
+# A GUI app, matched by its app_id.
+[apps.obsidian]
+command = "obsidian"
+is_terminal = false
+identifier = { AppId = "obsidian" }
+# startup_override = "obsidian --some-flag"
+"#;
+
+impl Spawn {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config_path =
+            env::home_dir().ok_or("could not determine home directory ($HOME is not set)")?;
+        config_path.push(".config/spawn/spawn.toml");
+
+        if !config_path.is_file() {
+            eprintln!(
+                "No config at '{}', writing a default one",
+                config_path.display()
+            );
+            let config_dir = config_path
+                .parent()
+                .ok_or("config path has no parent directory")?;
+            std::fs::create_dir_all(config_dir)?;
+            std::fs::write(&config_path, DEFAULT_CONFIG)?;
+        }
+
+        let contents = std::fs::read(&config_path)
+            .map_err(|e| format!("failed to read '{}': {}", config_path.display(), e))?;
+        toml::from_slice::<Spawn>(&contents)
+            .map_err(|e| format!("failed to parse '{}': {}", config_path.display(), e).into())
+    }
+
+    /// Execute swaymsg command and return output
+    fn swaymsg(&self, args: &[&str]) -> Result<Output, std::io::Error> {
+        Command::new("swaymsg").args(args).output()
+    }
+
+    /// Ask the `spawn-daemon` for the current window list, starting it if
+    /// it isn't running yet.
+    pub fn get_windows(&self) -> Result<Vec<SwayWindow>, Box<dyn std::error::Error>> {
+        match query_daemon_autostart(&Request::ListWindows)? {
+            Response::Windows(windows) => Ok(windows),
+            Response::Error(e) => Err(e.into()),
+            Response::Ok => Err("spawn-daemon returned an unexpected reply".into()),
+        }
+    }
+
+    fn is_running(&self, windows: &[SwayWindow], identifiers: &[WindowIdentifier]) -> bool {
+        windows.iter().any(|w| matches_any_identifier(w, identifiers))
+    }
+
+    fn is_focused(&self, windows: &[SwayWindow], identifiers: &[WindowIdentifier]) -> bool {
+        windows.iter().any(|w| {
+            if !w.focused {
+                return false;
+            }
+
+            matches_any_identifier(w, identifiers)
+        })
+    }
+
+    /// Build the startup command for an application
+    fn build_startup_command(&self, config: &AppConfig) -> String {
+        // Use startup override if specified
+        if let Some(ref override_cmd) = config.startup_override {
+            return override_cmd.clone();
+        }
+
+        if config.is_terminal {
+            let title = config.identifier.iter().find_map(|id| match id {
+                WindowIdentifier::Title(title) => Some(title),
+                _ => None,
+            });
+            if let Some(title) = title {
+                return format!(
+                    "{} --title {} --command {}",
+                    self.terminal, title, config.command
+                );
+            }
+        }
+
+        // Return command as-is for GUI applications
+        config.command.clone()
+    }
+
+    /// Build sway criteria string for window selection
+    fn build_criteria(&self, identifier: &WindowIdentifier) -> String {
+        match identifier {
+            WindowIdentifier::Title(title) => format!("[title=\"{}\"]", escape_criteria_value(title)),
+            WindowIdentifier::AppId(app_id) => {
+                format!("[app_id=\"{}\"]", escape_criteria_value(app_id))
+            }
+            WindowIdentifier::Class(class) => format!("[class=\"{}\"]", escape_criteria_value(class)),
+        }
+    }
+
+    /// Combine each identifier's criteria with `action` into one swaymsg
+    /// invocation, e.g. `[title="a"] focus; [app_id="b"] focus` - a comma
+    /// would chain `action` onto the *same* criteria, so each pair needs its
+    /// own `;`-separated group for sway to run `action` against whichever
+    /// criteria actually matches a window.
+    fn build_combined_command(&self, identifiers: &[WindowIdentifier], action: &str) -> String {
+        identifiers
+            .iter()
+            .map(|identifier| format!("{} {}", self.build_criteria(identifier), action))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    pub fn focus_window(
+        &self,
+        identifiers: &[WindowIdentifier],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let command = self.build_combined_command(identifiers, "focus");
+        self.swaymsg(&[&command])?;
+        Ok(())
+    }
+
+    fn move_to_scratchpad(
+        &self,
+        identifiers: &[WindowIdentifier],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let command = self.build_combined_command(identifiers, "move scratchpad");
+        self.swaymsg(&[&command])?;
+        Ok(())
+    }
+
+    /// Main logic to handle window toggling
+    pub fn handle_window(&self, app_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let config = self
+            .apps
+            .get(app_name)
+            .ok_or_else(|| format!("Unknown application: {}", app_name))?;
+
+        let windows = self.get_windows()?;
+
+        if self.is_running(&windows, &config.identifier) {
+            if self.is_focused(&windows, &config.identifier) {
+                // Window is focused -> move to scratchpad
+                self.move_to_scratchpad(&config.identifier)?;
+            } else {
+                // Window exists but not focused -> bring to focus
+                self.focus_window(&config.identifier)?;
+            }
+        } else {
+            // Window doesn't exist -> start it
+            let cmd = self.build_startup_command(config);
+            self.swaymsg(&["exec", &cmd])?;
+        }
+
+        Ok(())
+    }
+
+    /// List every running window through `self.launcher` and focus whichever
+    /// one the user picks.
+    pub fn pick(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let windows = self.get_windows()?;
+        if windows.is_empty() {
+            return Err("no windows are currently open".into());
+        }
+
+        let menu: String = windows
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                format!(
+                    "{}: {} / {} / {}\n",
+                    i,
+                    w.app_id.as_deref().unwrap_or("?"),
+                    w.title.as_deref().unwrap_or(""),
+                    w.workspace.as_deref().unwrap_or("?")
+                )
+            })
+            .collect();
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.launcher)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        child
+            .stdin
+            .take()
+            .ok_or("failed to open launcher stdin")?
+            .write_all(menu.as_bytes())?;
+
+        let mut selection = String::new();
+        child
+            .stdout
+            .take()
+            .ok_or("failed to open launcher stdout")?
+            .read_to_string(&mut selection)?;
+        child.wait()?;
+
+        let index: usize = selection
+            .split(':')
+            .next()
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or("no window selected")?;
+
+        let window = windows
+            .get(index)
+            .ok_or("launcher returned an unknown selection")?;
+
+        self.focus_window(&[identifier_for_window(window)])
+    }
+}
@@ -0,0 +1,440 @@
+//! wmfocus-style hint overlay: label every visible window and focus
+//! whichever one the user types the label for. Gated behind the `hint`
+//! cargo feature since it pulls in a layer-shell/cairo rendering stack the
+//! default build doesn't need.
+
+use std::error::Error;
+
+use smithay_client_toolkit::compositor::{CompositorHandler, CompositorState};
+use smithay_client_toolkit::delegate_compositor;
+use smithay_client_toolkit::delegate_keyboard;
+use smithay_client_toolkit::delegate_layer;
+use smithay_client_toolkit::delegate_output;
+use smithay_client_toolkit::delegate_registry;
+use smithay_client_toolkit::delegate_seat;
+use smithay_client_toolkit::delegate_shm;
+use smithay_client_toolkit::output::{OutputHandler, OutputState};
+use smithay_client_toolkit::registry::{ProvidesRegistryState, RegistryState};
+use smithay_client_toolkit::registry_handlers;
+use smithay_client_toolkit::seat::keyboard::{KeyEvent, KeyboardHandler, Keysym};
+use smithay_client_toolkit::seat::{Capability, SeatHandler, SeatState};
+use smithay_client_toolkit::shell::wlr_layer::{
+    Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface,
+};
+use smithay_client_toolkit::shm::slot::SlotPool;
+use smithay_client_toolkit::shm::{Shm, ShmHandler};
+
+use smithay_client_toolkit::reexports::calloop::EventLoop;
+use smithay_client_toolkit::reexports::calloop_wayland_source::WaylandSource;
+use smithay_client_toolkit::reexports::client::protocol::{
+    wl_keyboard::WlKeyboard, wl_output::WlOutput, wl_seat::WlSeat,
+};
+use smithay_client_toolkit::reexports::client::{globals::registry_queue_init, Connection, QueueHandle};
+
+use sway_spawn::config::{identifier_for_window, Spawn};
+use sway_spawn::window::SwayWindow;
+
+/// Home-row-first alphabet hint labels are drawn from, so the most common
+/// labels are the easiest to reach.
+const DEFAULT_ALPHABET: &str = "asdfjklgh";
+
+/// A window paired with the label the user types to focus it.
+struct Hint {
+    window: SwayWindow,
+    label: String,
+}
+
+/// Assign a label to each window, using two-character sequences once there
+/// are more windows than single letters in `alphabet`.
+fn assign_hints(windows: &[SwayWindow], alphabet: &str) -> Vec<Hint> {
+    let letters: Vec<char> = alphabet.chars().collect();
+    let depth = if windows.len() <= letters.len() { 1 } else { 2 };
+
+    windows
+        .iter()
+        .enumerate()
+        .map(|(i, window)| {
+            let mut label = String::new();
+            let mut n = i;
+            for _ in 0..depth {
+                label.push(letters[n % letters.len()]);
+                n /= letters.len();
+            }
+            Hint {
+                window: window.clone(),
+                label,
+            }
+        })
+        .collect()
+}
+
+/// Draw a hint label over every visible window, wait for the user to type
+/// one, and focus the matching window.
+pub fn run(spawn: &Spawn, windows: &[SwayWindow]) -> Result<(), Box<dyn Error>> {
+    // Background workspaces on an output keep a stable rect (they share
+    // their output's geometry), so a nonzero rect alone doesn't mean a
+    // window is actually on screen - it also needs to be on the workspace
+    // currently shown on its output.
+    let visible: Vec<SwayWindow> = windows
+        .iter()
+        .filter(|w| w.rect.width > 0 && w.rect.height > 0 && w.workspace_visible)
+        .cloned()
+        .collect();
+    if visible.is_empty() {
+        return Err("no visible windows to hint".into());
+    }
+
+    let hints = assign_hints(&visible, DEFAULT_ALPHABET);
+    let chosen = HintOverlay::show_and_wait(hints)?.ok_or("hint overlay was cancelled")?;
+
+    spawn.focus_window(&[identifier_for_window(&chosen)])
+}
+
+/// A layer-shell surface together with the logical geometry sway reported
+/// for its output, so `draw` can size the buffer to the real output and
+/// translate each hint's global `rect` into this output's local space.
+#[derive(Clone)]
+struct OutputSurface {
+    layer: LayerSurface,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+/// Per-output overlay state: one layer-shell surface and cairo-rendered
+/// buffer per output, plus the keyboard grab used to read the typed label.
+struct HintOverlay {
+    registry_state: RegistryState,
+    seat_state: SeatState,
+    output_state: OutputState,
+    compositor_state: CompositorState,
+    shm: Shm,
+    layer_shell: LayerShell,
+    pool: SlotPool,
+
+    hints: Vec<Hint>,
+    typed: String,
+    result: Option<SwayWindow>,
+    exit: bool,
+    surfaces: Vec<OutputSurface>,
+    keyboard: Option<WlKeyboard>,
+}
+
+impl HintOverlay {
+    /// Open a wayland connection, create an overlay `LayerSurface` per
+    /// output, render every hint label onto it, grab the keyboard and run
+    /// the event loop until a label is typed (or Escape cancels).
+    fn show_and_wait(hints: Vec<Hint>) -> Result<Option<SwayWindow>, Box<dyn Error>> {
+        let connection = Connection::connect_to_env()?;
+        let (globals, event_queue) = registry_queue_init(&connection)?;
+        let qh = event_queue.handle();
+
+        let mut event_loop: EventLoop<Self> = EventLoop::try_new()?;
+        WaylandSource::new(connection.clone(), event_queue)
+            .insert(event_loop.handle())
+            .map_err(|e| format!("failed to register wayland event source: {e}"))?;
+
+        let compositor_state = CompositorState::bind(&globals, &qh)?;
+        let layer_shell = LayerShell::bind(&globals, &qh)?;
+        let shm = Shm::bind(&globals, &qh)?;
+        let pool = SlotPool::new(4096, &shm)?;
+
+        let mut state = Self {
+            registry_state: RegistryState::new(&globals),
+            seat_state: SeatState::new(&globals, &qh),
+            output_state: OutputState::new(&globals, &qh),
+            compositor_state,
+            shm,
+            layer_shell,
+            pool,
+            hints,
+            typed: String::new(),
+            result: None,
+            exit: false,
+            surfaces: Vec::new(),
+            keyboard: None,
+        };
+
+        // Surfaces are created lazily as outputs are reported in
+        // `new_output`; draw once we know about at least one.
+        while !state.exit {
+            event_loop.dispatch(std::time::Duration::from_millis(16), &mut state)?;
+        }
+
+        Ok(state.result.take())
+    }
+
+    fn create_surface_for_output(&mut self, qh: &QueueHandle<Self>, output: WlOutput) {
+        // sway's window rects are in global/absolute coordinates, so we need
+        // this output's logical position (to translate them) and logical
+        // size (to size the buffer to the real output, not a guess) before
+        // we can draw anything sensible on it.
+        let info = self.output_state.info(&output);
+        let (x, y) = info
+            .as_ref()
+            .and_then(|i| i.logical_position)
+            .unwrap_or((0, 0));
+        let (width, height) = info.and_then(|i| i.logical_size).unwrap_or((1920, 1080));
+
+        let surface = self.compositor_state.create_surface(qh);
+        let layer = self.layer_shell.create_layer_surface(
+            qh,
+            surface,
+            Layer::Overlay,
+            Some("spawn-hint"),
+            Some(&output),
+        );
+        layer.set_anchor(Anchor::all());
+        layer.set_exclusive_zone(-1);
+        layer.set_keyboard_interactivity(KeyboardInteractivity::Exclusive);
+        layer.set_size(width as u32, height as u32);
+        layer.commit();
+        self.surfaces.push(OutputSurface {
+            layer,
+            x,
+            y,
+            width,
+            height,
+        });
+        self.draw(qh);
+    }
+
+    /// Paint every hint's label into each output's surface buffer, with the
+    /// text's baseline starting at the centre of its window's on-screen rect
+    /// (the label is not offset by its own size).
+    fn draw(&mut self, qh: &QueueHandle<Self>) {
+        for output_surface in self.surfaces.clone() {
+            let OutputSurface {
+                layer,
+                x: origin_x,
+                y: origin_y,
+                width,
+                height,
+            } = output_surface;
+            let width = width.max(1) as u32;
+            let height = height.max(1) as u32;
+            let Ok((buffer, canvas)) =
+                self.pool
+                    .create_buffer(width as i32, height as i32, (width * 4) as i32, wl_shm_format())
+            else {
+                continue;
+            };
+
+            // ARGB8888 canvas: clear to transparent, then draw each label
+            // with cairo at its window's rect, translated from global
+            // coordinates into this output's local space.
+            let cairo_surface = unsafe {
+                cairo::ImageSurface::create_for_data_unsafe(
+                    canvas.as_mut_ptr(),
+                    cairo::Format::ARgb32,
+                    width as i32,
+                    height as i32,
+                    (width * 4) as i32,
+                )
+            };
+            if let Ok(surface) = cairo_surface {
+                let ctx = cairo::Context::new(&surface).expect("cairo context");
+                ctx.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+                let _ = ctx.paint();
+                ctx.select_font_face(
+                    "monospace",
+                    cairo::FontSlant::Normal,
+                    cairo::FontWeight::Bold,
+                );
+                ctx.set_font_size(24.0);
+                ctx.set_source_rgba(1.0, 0.85, 0.0, 0.95);
+                for hint in &self.hints {
+                    let x = (hint.window.rect.x - origin_x) as f64
+                        + hint.window.rect.width as f64 / 2.0;
+                    let y = (hint.window.rect.y - origin_y) as f64
+                        + hint.window.rect.height as f64 / 2.0;
+                    ctx.move_to(x, y);
+                    let _ = ctx.show_text(&hint.label);
+                }
+            }
+
+            let surface = layer.wl_surface();
+            surface.attach(Some(buffer.wl_buffer()), 0, 0);
+            surface.damage_buffer(0, 0, width as i32, height as i32);
+            surface.commit();
+        }
+        let _ = qh;
+    }
+}
+
+fn wl_shm_format() -> smithay_client_toolkit::reexports::client::protocol::wl_shm::Format {
+    smithay_client_toolkit::reexports::client::protocol::wl_shm::Format::Argb8888
+}
+
+impl CompositorHandler for HintOverlay {
+    fn scale_factor_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: i32,
+    ) {
+    }
+    fn transform_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: smithay_client_toolkit::reexports::client::protocol::wl_output::Transform,
+    ) {
+    }
+    fn frame(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: u32,
+    ) {
+    }
+    fn surface_enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: &WlOutput,
+    ) {
+    }
+    fn surface_leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: &WlOutput,
+    ) {
+    }
+}
+
+impl OutputHandler for HintOverlay {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.output_state
+    }
+    fn new_output(&mut self, _: &Connection, qh: &QueueHandle<Self>, output: WlOutput) {
+        self.create_surface_for_output(qh, output);
+    }
+    fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, _: WlOutput) {}
+    fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: WlOutput) {}
+}
+
+impl LayerShellHandler for HintOverlay {
+    fn closed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &LayerSurface) {
+        self.exit = true;
+    }
+    fn configure(
+        &mut self,
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+        _: &LayerSurface,
+        _: smithay_client_toolkit::shell::wlr_layer::LayerSurfaceConfigure,
+        _: u32,
+    ) {
+        self.draw(qh);
+    }
+}
+
+impl SeatHandler for HintOverlay {
+    fn seat_state(&mut self) -> &mut SeatState {
+        &mut self.seat_state
+    }
+    fn new_seat(&mut self, _: &Connection, _: &QueueHandle<Self>, _: WlSeat) {}
+    fn new_capability(
+        &mut self,
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+        seat: WlSeat,
+        capability: Capability,
+    ) {
+        if capability == Capability::Keyboard && self.keyboard.is_none() {
+            if let Ok(keyboard) = self.seat_state.get_keyboard(qh, &seat, None) {
+                self.keyboard = Some(keyboard);
+            }
+        }
+    }
+    fn remove_capability(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: WlSeat,
+        _: Capability,
+    ) {
+    }
+    fn remove_seat(&mut self, _: &Connection, _: &QueueHandle<Self>, _: WlSeat) {}
+}
+
+impl KeyboardHandler for HintOverlay {
+    fn enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlKeyboard,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: u32,
+        _: &[u32],
+        _: &[Keysym],
+    ) {
+    }
+    fn leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlKeyboard,
+        _: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+        _: u32,
+    ) {
+    }
+
+    fn press_key(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlKeyboard, _: u32, event: KeyEvent) {
+        if event.keysym == Keysym::Escape {
+            self.exit = true;
+            return;
+        }
+
+        if let Some(ch) = event.utf8.and_then(|s| s.chars().next()) {
+            self.typed.push(ch);
+            if let Some(hint) = self.hints.iter().find(|h| h.label == self.typed) {
+                self.result = Some(hint.window.clone());
+                self.exit = true;
+            } else if !self.hints.iter().any(|h| h.label.starts_with(&self.typed)) {
+                self.typed.clear();
+            }
+        }
+    }
+
+    fn release_key(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlKeyboard, _: u32, _: KeyEvent) {}
+
+    fn update_modifiers(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlKeyboard,
+        _: smithay_client_toolkit::seat::keyboard::Modifiers,
+        _: u32,
+    ) {
+    }
+}
+
+impl ShmHandler for HintOverlay {
+    fn shm_state(&mut self) -> &mut Shm {
+        &mut self.shm
+    }
+}
+
+impl ProvidesRegistryState for HintOverlay {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry_state
+    }
+    registry_handlers![OutputState, SeatState];
+}
+
+delegate_compositor!(HintOverlay);
+delegate_output!(HintOverlay);
+delegate_shm!(HintOverlay);
+delegate_seat!(HintOverlay);
+delegate_keyboard!(HintOverlay);
+delegate_layer!(HintOverlay);
+delegate_registry!(HintOverlay);
@@ -0,0 +1,83 @@
+//! Wire protocol and socket addressing for talking to `spawn-daemon`.
+
+use crate::window::SwayWindow;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+/// A request sent from the CLI client to the daemon, one per line of JSON.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Request {
+    /// Return every currently live window.
+    ListWindows,
+    /// Focus the window that was focused just before the current one.
+    SwitchLru,
+    /// Focus the next urgent window, falling back to LRU if none is urgent.
+    SwitchUrgent,
+}
+
+/// The daemon's reply to a [`Request`], one per line of JSON.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Response {
+    Windows(Vec<SwayWindow>),
+    Ok,
+    Error(String),
+}
+
+/// Path of the daemon's Unix socket, rooted under `$XDG_RUNTIME_DIR`.
+pub fn socket_path() -> PathBuf {
+    let runtime_dir = env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+    let mut path = PathBuf::from(runtime_dir);
+    path.push("spawn-daemon.sock");
+    path
+}
+
+/// Send a single request to `spawn-daemon` over its Unix socket and read
+/// back one line of JSON response.
+pub fn query_daemon(request: &Request) -> Result<Response, Box<dyn std::error::Error>> {
+    let mut stream = UnixStream::connect(socket_path())?;
+
+    let mut payload = serde_json::to_string(request)?;
+    payload.push('\n');
+    stream.write_all(payload.as_bytes())?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Launch `spawn-daemon` in the background and wait briefly for its socket
+/// to come up.
+pub fn spawn_daemon() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new("spawn-daemon").spawn().map_err(|e| {
+        format!(
+            "spawn-daemon is not running and could not be started ({}); \
+             is it installed and on $PATH?",
+            e
+        )
+    })?;
+
+    for _ in 0..20 {
+        if socket_path().exists() {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    Ok(())
+}
+
+/// Send a request to the daemon, starting it first if it isn't reachable.
+pub fn query_daemon_autostart(request: &Request) -> Result<Response, Box<dyn std::error::Error>> {
+    match query_daemon(request) {
+        Ok(response) => Ok(response),
+        Err(_) => {
+            spawn_daemon()?;
+            query_daemon(request)
+        }
+    }
+}
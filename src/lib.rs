@@ -0,0 +1,4 @@
+pub mod config;
+pub mod ipc;
+pub mod sway_ipc;
+pub mod window;
@@ -0,0 +1,91 @@
+//! Minimal client for sway's native IPC socket (see `man 7 sway-ipc`).
+//!
+//! This talks directly to the socket sway exposes at `$SWAYSOCK` instead of
+//! shelling out to `swaymsg`, which is required for the daemon to hold a
+//! persistent connection and subscribe to event streams.
+
+use serde_json::Value;
+use std::env;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+
+pub const MSG_RUN_COMMAND: u32 = 0;
+pub const MSG_GET_TREE: u32 = 4;
+pub const MSG_SUBSCRIBE: u32 = 2;
+pub const MSG_EVENT: u32 = 0x80000000;
+
+/// A connection to the sway IPC socket.
+pub struct SwayIpc {
+    stream: UnixStream,
+}
+
+impl SwayIpc {
+    /// Connect to the socket sway advertises via `$SWAYSOCK`.
+    pub fn connect() -> io::Result<Self> {
+        let path = env::var("SWAYSOCK")
+            .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "SWAYSOCK is not set"))?;
+        Ok(Self {
+            stream: UnixStream::connect(path)?,
+        })
+    }
+
+    fn send(&mut self, message_type: u32, payload: &str) -> io::Result<()> {
+        let body = payload.as_bytes();
+        self.stream.write_all(MAGIC)?;
+        self.stream.write_all(&(body.len() as u32).to_ne_bytes())?;
+        self.stream.write_all(&message_type.to_ne_bytes())?;
+        self.stream.write_all(body)?;
+        self.stream.flush()
+    }
+
+    /// Read a single framed message, returning its type and decoded payload.
+    pub fn recv(&mut self) -> io::Result<(u32, Value)> {
+        let mut header = [0u8; 14];
+        self.stream.read_exact(&mut header)?;
+        if &header[0..6] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "bad ipc magic"));
+        }
+        let len = u32::from_ne_bytes(header[6..10].try_into().unwrap()) as usize;
+        let message_type = u32::from_ne_bytes(header[10..14].try_into().unwrap());
+
+        let mut body = vec![0u8; len];
+        self.stream.read_exact(&mut body)?;
+        let value = serde_json::from_slice(&body)?;
+        Ok((message_type, value))
+    }
+
+    /// Fetch the current window tree.
+    pub fn get_tree(&mut self) -> io::Result<Value> {
+        self.send(MSG_GET_TREE, "")?;
+        let (_, tree) = self.recv()?;
+        Ok(tree)
+    }
+
+    /// Run a raw sway command (e.g. `"[con_id=123] focus"`).
+    pub fn run_command(&mut self, command: &str) -> io::Result<()> {
+        self.send(MSG_RUN_COMMAND, command)?;
+        self.recv()?;
+        Ok(())
+    }
+
+    /// Subscribe to the given event types. After this call, `recv` yields
+    /// `window`/`workspace` events instead of command replies.
+    pub fn subscribe(&mut self, events: &[&str]) -> io::Result<()> {
+        let payload = serde_json::to_string(events)?;
+        self.send(MSG_SUBSCRIBE, &payload)?;
+        self.recv()?;
+        Ok(())
+    }
+
+    /// Clone the underlying socket handle. Both handles share one connection
+    /// and one read/write position, so frames sent or read through one can
+    /// interleave with the other's; use `connect` for an independent
+    /// connection.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self {
+            stream: self.stream.try_clone()?,
+        })
+    }
+}
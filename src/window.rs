@@ -0,0 +1,128 @@
+//! Sway window tree model shared between the CLI client and the daemon.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SwayWindow {
+    pub id: i64,
+    #[serde(rename = "name")]
+    pub title: Option<String>,
+    pub app_id: Option<String>,
+    pub focused: bool,
+    pub urgent: bool,
+    pub window_properties: Option<WindowProperties>,
+    #[serde(rename = "type")]
+    pub window_type: String,
+    /// On-screen geometry, used by the hint overlay and auto-tiling.
+    pub rect: Rect,
+    /// Name of the workspace this window lives on. Not present on the tree
+    /// node itself; filled in by [`extract_windows`] as it walks the tree.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Whether `workspace` is the one currently shown on its output. Sway
+    /// keeps a stable rect for windows on background workspaces (they share
+    /// their output's geometry), so this is needed to tell windows that are
+    /// actually on screen apart from ones that merely have a nonzero rect.
+    #[serde(default)]
+    pub workspace_visible: bool,
+    /// Name of the output (monitor) this window lives on, filled in the
+    /// same way as `workspace`.
+    #[serde(default)]
+    pub output: Option<String>,
+    /// Layout (`splith`, `splitv`, `stacked`, `tabbed`, ...) of this
+    /// window's immediate parent container, used to decide whether
+    /// auto-tiling should touch it.
+    #[serde(default)]
+    pub parent_layout: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WindowProperties {
+    pub class: Option<String>,
+}
+
+/// A window's on-screen geometry, as reported by sway's `get_tree`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Recursively extract windows from a sway `get_tree` node.
+pub fn extract_windows(node: &serde_json::Value, windows: &mut Vec<SwayWindow>) {
+    extract_windows_in(node, None, None, false, None, windows);
+}
+
+fn extract_windows_in(
+    node: &serde_json::Value,
+    output: Option<&str>,
+    workspace: Option<&str>,
+    workspace_visible: bool,
+    parent_layout: Option<&str>,
+    windows: &mut Vec<SwayWindow>,
+) {
+    let node_type = node.get("type").and_then(|t| t.as_str());
+
+    let output_here = if node_type == Some("output") {
+        node.get("name").and_then(|n| n.as_str())
+    } else {
+        output
+    };
+    let (workspace_here, workspace_visible_here) = if node_type == Some("workspace") {
+        (
+            node.get("name").and_then(|n| n.as_str()),
+            node.get("visible").and_then(|v| v.as_bool()).unwrap_or(false),
+        )
+    } else {
+        (workspace, workspace_visible)
+    };
+
+    if let Some(window_type) = node_type {
+        if window_type == "floating_con" || window_type == "con" {
+            if let Ok(mut window) = serde_json::from_value::<SwayWindow>(node.clone()) {
+                window.output = output_here.map(str::to_string);
+                window.workspace = workspace_here.map(str::to_string);
+                window.workspace_visible = workspace_visible_here;
+                // Floating windows aren't arranged by their parent's split
+                // layout, so auto-tiling should never touch them.
+                window.parent_layout = (window_type != "floating_con")
+                    .then(|| parent_layout.map(str::to_string))
+                    .flatten();
+                windows.push(window);
+            }
+        }
+    }
+
+    // Children of `nodes` are arranged per this node's own `layout`.
+    let child_layout = node.get("layout").and_then(|l| l.as_str());
+
+    // Recurse into child nodes
+    if let Some(nodes) = node.get("nodes").and_then(|n| n.as_array()) {
+        for child in nodes {
+            extract_windows_in(
+                child,
+                output_here,
+                workspace_here,
+                workspace_visible_here,
+                child_layout,
+                windows,
+            );
+        }
+    }
+
+    // Recurse into floating nodes - never tiled, so no parent layout.
+    if let Some(floating) = node.get("floating_nodes").and_then(|n| n.as_array()) {
+        for child in floating {
+            extract_windows_in(
+                child,
+                output_here,
+                workspace_here,
+                workspace_visible_here,
+                None,
+                windows,
+            );
+        }
+    }
+}